@@ -0,0 +1,62 @@
+use std::path::Path;
+
+use crate::{config, AnyError, Config, JObject};
+
+///Builds a `Config` that resolves reads against several precedence-ranked `JObject` layers (e.g. built-in defaults,
+///a home-dir file, a project-dir file, runtime overrides) while only ever saving the one writable file layer
+///# Usage
+/// Layers are merged with higher ranks winning on conflicting keys; `write_value`/`save` still only ever touch the
+/// writable layer handed to `build`/`build_from_home`, so defaults and overrides are never written back to disk.
+#[derive(Debug, Default)]
+pub struct ConfigBuilder {
+    layers: Vec<(i32, JObject)>,
+    concat_arrays: bool,
+}
+
+impl ConfigBuilder {
+
+    ///Creates an empty builder with no layers
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///Adds a read-only layer at the given precedence rank, higher ranks take priority over lower ones
+    #[must_use]
+    pub fn with_layer(mut self, rank: i32, data: JObject) -> Self {
+        self.layers.push((rank, data));
+        self
+    }
+
+    ///When set, arrays from higher-ranked layers are appended to lower-ranked ones instead of replacing them
+    #[must_use]
+    pub fn concat_arrays(mut self, concat: bool) -> Self {
+        self.concat_arrays = concat;
+        self
+    }
+
+    ///Opens the writable layer at the given precedence rank from the given folder path, merging in the builder's layers
+    ///# Arguments
+    ///* `rank` - Precedence rank of the writable file among the builder's layers
+    ///* `config_folder_path` - Path to the config folder, will create any missing folders
+    ///* `file_name` - Name of the config file, will create the file if it doesn't exist
+    pub fn build<P: AsRef<Path>>(self, rank: i32, config_folder_path: P, file_name: &str) -> Result<Config, AnyError>{
+        let mut built = config::open_from_path(config_folder_path, file_name)?;
+        built.set_layers(self.layers, rank, self.concat_arrays);
+
+        Ok(built)
+    }
+
+    ///Opens the writable layer at the given precedence rank from the home directory, merging in the builder's layers
+    ///# Arguments
+    ///* `rank` - Precedence rank of the writable file among the builder's layers
+    ///* `folder_path` - Path to the config folder, will create any missing folders
+    ///* `file_name` - Name of the config file, will create the file if it doesn't exist
+    pub fn build_from_home<P: AsRef<Path>>(self, rank: i32, folder_path: P, file_name: &str) -> Result<Config, AnyError>{
+        let mut built = config::open_from_home(folder_path, file_name)?;
+        built.set_layers(self.layers, rank, self.concat_arrays);
+
+        Ok(built)
+    }
+
+}
@@ -1,272 +1,600 @@
-use std::{path::{Path, PathBuf}, fs::{OpenOptions, File}, io::{Read, Write, Seek}, borrow::{Borrow, BorrowMut}, hash::Hash};
-
-use serde::{de::DeserializeOwned, Serialize};
-use serde_json::map::Entry;
-
-use crate::{JObject, AnyError};
-
-///Opens or create the given JSON config file within the given folder path
-///# Arguments
-///* `config_folder_path` - Path to the config folder, will create any missing folders
-///* `file_name` - Name of the config file, will create the file if it doesn't exist
-pub fn open_from_path<P: AsRef<Path>>(config_folder_path: P, file_name: &str) -> Result<Config, AnyError>{
-
-    let mut path = PathBuf::new();
-    path.push(config_folder_path);
-    std::fs::create_dir_all(&path)?;
-    path.push(file_name);
-
-    let mut file = OpenOptions::new()
-    .read(true)
-    .write(true)
-    .create(true)
-    .open(path)?;
-
-    let mut str_json = String::new();
-    file.read_to_string(&mut str_json)?;
-
-    let obj_config = if str_json.is_empty() {
-        JObject::new()
-    }
-    else {
-        serde_json::from_str::<JObject>(&str_json)?
-    };
-
-    Ok(Config { file, data: obj_config })
-}
-
-///Opens or create the given JSON config file within the given folder path appended to the result of `directories::BaseDirs::new().home_dir()`
-///# Arguments
-///* `folder_path` - Path to the config folder, will create any missing folders
-///* `file_name` - Name of the config file, will create the file if it doesn't exist
-pub fn open_from_home<P: AsRef<Path>>(folder_path: P, file_name: &str) -> Result<Config, AnyError>{
-    let dirs = directories::BaseDirs::new().ok_or("No valid home directory path could be retrived from OS")?;
-    let home = dirs.home_dir();
-    let mut buff = PathBuf::from(home);
-    buff.push(folder_path);
-    open_from_path(buff, file_name)
-}
-
-///Object representing an open config file, use `lib_config::open_from_path` or `lib_config::open_from_home` to get an instance
-///# Usage
-/// A `Config` may contain primitive values (such as strings or numbers), arrays or `Section`s. A `Section` is a JSON object that can contain the same values as a Config,
-/// useful to logically split the config file. To save the contents of the config call the `save()` associated function.
-#[derive(Debug)]
-pub struct Config{
-    file: File,
-    data: JObject,
-}
-
-impl Config {
-
-    ///Writes a valute to the given key, if it doesn't exist, inserts the key - value pair
-    pub fn write_value<K: Into<String>, V: Serialize>(&mut self, key: K, value: V) -> Result<(), AnyError>{
-        let key = key.into();
-        let jvalue = serde_json::to_value(value)?;
-
-        if let Entry::Vacant(e) = self.data.entry(&key) {
-            e.insert(jvalue);
-        }
-        else {
-            self.data[&key] = jvalue;
-        }
-
-        Ok(())
-    }
-
-    ///Reads a value from the given key, if the key does not exist returns `Err`
-    pub fn read_value<K: Into<String>, V: DeserializeOwned>(&self, key: K) -> Result<V, AnyError>{
-        let json = self.data.get(&key.into()).ok_or("Key not found")?.clone();
-        let value = serde_json::from_value::<V>(json)?;
-
-        Ok(value)
-    }
-
-    ///Reads a value from the given key, if the key does not exists, inserts it with the given value
-    pub fn read_or_insert<K: Into<String>, V: DeserializeOwned + Serialize + Clone>(&mut self, key: K, value: V) -> Result<V, AnyError>{
-        let key = key.into();
-
-        let v = if let Entry::Vacant(e) = self.data.entry(&key) {
-            let jvalue = serde_json::to_value(value.clone())?;
-            e.insert(jvalue);
-            value
-        }
-        else {
-            let jvalue = &self.data[&key];
-            serde_json::from_value(jvalue.clone())?
-        };
-
-        Ok(v)
-    }
-
-    ///Updates a value with the given key using the provided function, returns the final value of the key, if the key does not exist returns Err
-    pub fn update_value<K, V, Out, F>(&mut self, key: &K, f_upd: F) -> Result<Out, AnyError>
-    where
-        K: ?Sized + Ord + Eq + Hash,
-        String: Borrow<K>,
-        V: DeserializeOwned,
-        Out: Serialize,
-        F: FnOnce(&V) -> Out,
-    {
-        let input = self.data.get(key).ok_or("Key not found")?;
-        let value = serde_json::from_value(input.clone())?;
-        let out = f_upd(&value);
-        let jvalue = serde_json::to_value(&out)?;
-        self.data[key] = jvalue;
-
-        Ok(out)
-    }
-
-    ///Gets an immutable reference to `Section` at the given key
-    pub fn get_section<K>(&self, key: &K) -> Result<Section<&JObject>, AnyError>
-    where K: ?Sized + Ord + Eq + Hash, String: Borrow<K>{
-        let value = self.data.get(key).ok_or("Key not found")?
-        .as_object().ok_or("Key's Value is not a json object")?;
-
-        Ok(Section(value))
-    }
-
-    ///Gets a mutable reference to `Section` at the given key
-    ///# Remarks
-    /// Changing the `Section`'s value will also change the `Config` data
-    pub fn get_section_mut<K>(&mut self, key: &K) -> Result<Section<&mut JObject>, AnyError>
-    where K: ?Sized + Ord + Eq + Hash, String: Borrow<K>{
-        let value = self.data.get_mut(key).ok_or("Key not found")?
-        .as_object_mut().ok_or("Key's Value is not a json object")?;
-
-        Ok(Section(value))
-    }
-
-    ///Writes the `Config` object to the file
-    pub fn save(&mut self) -> Result<String, AnyError>{
-        let str = serde_json::to_string_pretty(&self.data)?;
-
-        self.file.set_len(0)?;
-        self.file.rewind()?;
-        self.file.write_all(str.as_bytes())?;
-
-        Ok(str)
-    }
-
-    ///Clones the `Config` data, the result does not have any reference to the original `Config`
-    #[must_use]
-    pub fn clone_data(&self) -> JObject{
-        self.data.clone()
-    }
-
-    ///Replaces `Config` data with the provided data
-    pub fn copy_from(&mut self, data: JObject){
-        self.data = data;
-    }
-
-}
-
-///Part of a `Config` object, may contain sub-sections
-#[derive(Debug)]
-pub struct Section<T: ?Sized + Borrow<JObject>>(T);
-
-impl<T: ?Sized + Borrow<JObject>> Section<T>{
-
-    ///Reads a value from the given key, if the key does not exist returns `Err`
-    pub fn read_value<K, V>(&self, key: &K) -> Result<V, AnyError>
-    where
-        K: ?Sized + Ord + Eq + Hash,
-        String: Borrow<K>,
-        V: DeserializeOwned
-    {
-        let json = self.0.borrow().get(key).ok_or("Key not found")?.clone();
-        let value = serde_json::from_value::<V>(json)?;
-
-        Ok(value)
-    }
-
-    ///Gets an immutable reference to `Section` at the given key
-    pub fn get_section<K>(&self, key: &K) -> Result<Section<&JObject>, AnyError>
-    where K: ?Sized + Ord + Eq + Hash, String: Borrow<K>{
-        let value = self.0.borrow().get(key).ok_or("Key not found")?
-        .as_object().ok_or("Key's Value is not a json object")?;
-
-        Ok(Section(value))
-    }
-
-    ///Clones the `Section` data, the result does not have any reference to the original `Config` nor `Section`
-    #[must_use]
-    pub fn clone_data(&self) -> JObject{
-        self.0.borrow().clone()
-    }
-    
-}
-
-impl<T: ?Sized + BorrowMut<JObject>> Section<T>{
-
-    ///Writes a valute to the given key, if it doesn't exist, inserts the key - value pair
-    pub fn write_value<K: Into<String>, V: Serialize>(&mut self, key: K, value: V) -> Result<(), AnyError>{
-        let key = key.into();
-        let jvalue = serde_json::to_value(value)?;
-
-        if let Entry::Vacant(e) = self.0.borrow_mut().entry(&key) {
-            e.insert(jvalue);
-        }
-        else {
-            self.0.borrow_mut()[&key] = jvalue;
-        }
-
-        Ok(())
-    }
-
-    ///Reads a value from the given key, if the key does not exists, inserts it with the given value
-    pub fn read_or_insert<K: Into<String>, V: DeserializeOwned + Serialize + Clone>(&mut self, key: K, value: V) -> Result<V, AnyError>{
-        let key = key.into();
-
-        let v = if let Entry::Vacant(e) = self.0.borrow_mut().entry(&key) {
-            let jvalue = serde_json::to_value(value.clone())?;
-            e.insert(jvalue);
-            value
-        }
-        else {
-            let jvalue = &self.0.borrow()[&key];
-            serde_json::from_value(jvalue.clone())?
-        };
-
-        Ok(v)
-    }
-
-    ///Updates a value with the given key using the provided function, returns the final value of the key, if the key does not exist returns Err
-    pub fn update_value<K, V, Out, F>(&mut self, key: &K, f_upd: F) -> Result<Out, AnyError>
-    where
-        K: ?Sized + Ord + Eq + Hash,
-        String: Borrow<K>,
-        V: DeserializeOwned,
-        Out: Serialize,
-        F: FnOnce(&V) -> Out,
-    {
-        let input = self.0.borrow().get(key).ok_or("Key not found")?;
-        let value = serde_json::from_value(input.clone())?;
-        let out = f_upd(&value);
-        let jvalue = serde_json::to_value(&out)?;
-        self.0.borrow_mut()[key] = jvalue;
-
-        Ok(out)
-    }
-
-    ///Gets a mutable reference to `Section` at the given key
-    ///# Remarks
-    /// Changing the `Section`'s value will also change the `Config` data
-    pub fn get_section_mut<K>(&mut self, key: &K) -> Result<Section<&mut JObject>, AnyError>
-    where K: ?Sized + Ord + Eq + Hash, String: Borrow<K>{
-        let value = self.0.borrow_mut().get_mut(key).ok_or("Key not found")?
-        .as_object_mut().ok_or("Key's Value is not a json object")?;
-
-        Ok(Section(value))
-    }
-
-    ///Replaces `Section` data with the provided data
-    pub fn copy_from(&mut self, data: JObject){
-        self.0.borrow_mut().clear();
-
-        for (k, v) in data {
-            self.0.borrow_mut().insert(k, v);
-        }
-    }
-
-}
+use std::{path::{Path, PathBuf}, fs::{OpenOptions, File}, io::Write, borrow::{Borrow, BorrowMut, Cow}, hash::Hash};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::map::Entry;
+
+use crate::{env, format::{self, Format}, merge, path, JObject, AnyError};
+
+///Opens or creates the given config file within the given folder path, inferring its `Format` from `file_name`'s extension (defaults to JSON)
+///# Arguments
+///* `config_folder_path` - Path to the config folder, will create any missing folders
+///* `file_name` - Name of the config file, will create the file if it doesn't exist
+pub fn open_from_path<P: AsRef<Path>>(config_folder_path: P, file_name: &str) -> Result<Config, AnyError>{
+    open_from_path_with_format(config_folder_path, file_name, format::infer_format(file_name))
+}
+
+///Opens or creates the given config file within the given folder path, using the given `Format` instead of inferring one
+///# Arguments
+///* `config_folder_path` - Path to the config folder, will create any missing folders
+///* `file_name` - Name of the config file, will create the file if it doesn't exist
+///* `format` - The `Format` used to parse the file and, later, to serialize it back on `save`
+///# Remarks
+/// Fails immediately with an `Err` if the file's advisory lock is already held, rather than blocking - including
+/// by another `Config` opened on the same path earlier in this same process
+pub fn open_from_path_with_format<P: AsRef<Path>>(config_folder_path: P, file_name: &str, format: Box<dyn Format>) -> Result<Config, AnyError>{
+
+    let mut path = PathBuf::new();
+    path.push(config_folder_path);
+    std::fs::create_dir_all(&path)?;
+    path.push(file_name);
+
+    //Only used to create the file if missing, the actual content is read through `std::fs::read_to_string` below
+    OpenOptions::new().write(true).create(true).truncate(false).open(&path)?;
+
+    let lock_file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(lock_path(&path))?;
+    //Non-blocking: a blocking `lock()` would hang forever (no timeout) if this same process already holds the
+    //lock, e.g. two modules each opening their own `Config` for the same path. `try_lock` lets us fail fast instead.
+    lock_file.try_lock().map_err(|err| match err {
+        std::fs::TryLockError::WouldBlock => AnyError::Config("Config file is already locked by another open Config handle"),
+        std::fs::TryLockError::Error(io_err) => io_err.into(),
+    })?; // held across the `Config`'s lifetime, see `Config::_lock_file`
+
+    let str_content = std::fs::read_to_string(&path)?;
+
+    let obj_config = if str_content.is_empty() {
+        JObject::new()
+    }
+    else {
+        format.parse(&str_content)?
+    };
+
+    Ok(Config { path, _lock_file: lock_file, data: obj_config, layers: Vec::new(), rank: 0, concat_arrays: false, env_prefix: None, format, dirty: false, backup_max_files: None, backup_max_size: None, secure: false })
+}
+
+///Appends `.lock` to `path`, giving the sibling advisory-lock file used to serialize writers to the same config file
+fn lock_path(path: &Path) -> PathBuf{
+    let mut lock = path.as_os_str().to_os_string();
+    lock.push(".lock");
+    PathBuf::from(lock)
+}
+
+///Appends `.tmp` to `path`, giving the sibling scratch file `save()` writes to before atomically renaming it over `path`
+fn tmp_path(path: &Path) -> PathBuf{
+    let mut tmp = path.as_os_str().to_os_string();
+    tmp.push(".tmp");
+    PathBuf::from(tmp)
+}
+
+///Appends `.{n}` to `path`, giving the `n`th rotated backup written by `save()` when `with_backups` is set
+fn backup_path(path: &Path, n: u32) -> PathBuf{
+    let mut backup = path.as_os_str().to_os_string();
+    backup.push(format!(".{n}"));
+    PathBuf::from(backup)
+}
+
+///Opens or creates the given config file within the given folder path appended to the result of `directories::BaseDirs::new().home_dir()`, inferring its `Format` from `file_name`'s extension (defaults to JSON)
+///# Arguments
+///* `folder_path` - Path to the config folder, will create any missing folders
+///* `file_name` - Name of the config file, will create the file if it doesn't exist
+pub fn open_from_home<P: AsRef<Path>>(folder_path: P, file_name: &str) -> Result<Config, AnyError>{
+    open_from_home_with_format(folder_path, file_name, format::infer_format(file_name))
+}
+
+///Opens or creates the given config file within the given folder path appended to the result of `directories::BaseDirs::new().home_dir()`, using the given `Format` instead of inferring one
+///# Arguments
+///* `folder_path` - Path to the config folder, will create any missing folders
+///* `file_name` - Name of the config file, will create the file if it doesn't exist
+///* `format` - The `Format` used to parse the file and, later, to serialize it back on `save`
+pub fn open_from_home_with_format<P: AsRef<Path>>(folder_path: P, file_name: &str, format: Box<dyn Format>) -> Result<Config, AnyError>{
+    let dirs = directories::BaseDirs::new().ok_or("No valid home directory path could be retrived from OS")?;
+    let home = dirs.home_dir();
+    let mut buff = PathBuf::from(home);
+    buff.push(folder_path);
+    open_from_path_with_format(buff, file_name, format)
+}
+
+///Object representing an open config file, use `lib_config::open_from_path` or `lib_config::open_from_home` to get an instance
+///# Usage
+/// A `Config` may contain primitive values (such as strings or numbers), arrays or `Section`s. A `Section` is a JSON object that can contain the same values as a Config,
+/// useful to logically split the config file. To save the contents of the config call the `save()` associated function.
+pub struct Config{
+    path: PathBuf,
+    //Held only to keep the advisory lock alive for the lifetime of the `Config`; released when dropped
+    _lock_file: File,
+    data: JObject,
+    layers: Vec<(i32, JObject)>,
+    rank: i32,
+    concat_arrays: bool,
+    env_prefix: Option<String>,
+    format: Box<dyn Format>,
+    dirty: bool,
+    backup_max_files: Option<u32>,
+    backup_max_size: Option<u64>,
+    secure: bool,
+}
+
+impl std::fmt::Debug for Config {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Config")
+        .field("path", &self.path)
+        .field("data", &self.data)
+        .field("layers", &self.layers)
+        .field("rank", &self.rank)
+        .field("concat_arrays", &self.concat_arrays)
+        .field("env_prefix", &self.env_prefix)
+        .field("format", &self.format)
+        .field("dirty", &self.dirty)
+        .field("backup_max_files", &self.backup_max_files)
+        .field("backup_max_size", &self.backup_max_size)
+        .field("secure", &self.secure)
+        .finish_non_exhaustive()
+    }
+}
+
+impl Config {
+
+    ///Installs the layers, precedence rank and array-merge policy produced by a `ConfigBuilder`
+    pub(crate) fn set_layers(&mut self, layers: Vec<(i32, JObject)>, rank: i32, concat_arrays: bool){
+        self.layers = layers;
+        self.rank = rank;
+        self.concat_arrays = concat_arrays;
+    }
+
+    ///Overlays environment variables prefixed with `prefix` (e.g. `MYAPP_PORT`, `MYAPP_DB__HOST`) as the highest-precedence layer
+    ///# Remarks
+    /// Only affects values resolved by `read_value`/`read_path` and the pre-update value read by
+    /// `update_value`/`update_path`; the overlay itself is never written back by `save`
+    #[must_use]
+    pub fn with_env_prefix<S: Into<String>>(mut self, prefix: S) -> Self{
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    ///Enables rotating backups on `save()`, keeping up to `max_files` previous versions of the config alongside it
+    ///(e.g. `config.json.1` is the most recent, `config.json.2` the one before that, ...)
+    #[must_use]
+    pub fn with_backups(mut self, max_files: u32) -> Self{
+        self.backup_max_files = Some(max_files);
+        self
+    }
+
+    ///Gates rotation on the on-disk file exceeding `max_size` bytes, instead of cutting a backup on every `save()`
+    ///# Remarks
+    /// Has no effect unless `with_backups` was also set
+    #[must_use]
+    pub fn with_backup_max_size(mut self, max_size: u64) -> Self{
+        self.backup_max_size = Some(max_size);
+        self
+    }
+
+    ///Returns the deeply-merged view of `data`, any layers added through a `ConfigBuilder` and the env overlay
+    ///# Remarks
+    /// Has no extra cost when the `Config` was not built with layers nor an env prefix
+    fn resolved(&self) -> Cow<'_, JObject>{
+        if self.layers.is_empty() && self.env_prefix.is_none() {
+            return Cow::Borrowed(&self.data);
+        }
+
+        let mut ranked: Vec<(i32, Cow<JObject>)> = self.layers.iter()
+        .map(|(rank, data)| (*rank, Cow::Borrowed(data)))
+        .collect();
+        ranked.push((self.rank, Cow::Borrowed(&self.data)));
+
+        if let Some(prefix) = &self.env_prefix {
+            ranked.push((i32::MAX, Cow::Owned(env::collect_layer(prefix))));
+        }
+
+        ranked.sort_by_key(|(rank, _)| *rank);
+
+        let mut merged = JObject::new();
+        for (_, layer) in ranked {
+            merge::merge_into(&mut merged, &layer, self.concat_arrays);
+        }
+
+        Cow::Owned(merged)
+    }
+
+    ///Writes a valute to the given key, if it doesn't exist, inserts the key - value pair
+    pub fn write_value<K: Into<String>, V: Serialize>(&mut self, key: K, value: V) -> Result<(), AnyError>{
+        let key = key.into();
+        let jvalue = serde_json::to_value(value)?;
+
+        if let Entry::Vacant(e) = self.data.entry(&key) {
+            e.insert(jvalue);
+        }
+        else {
+            self.data[&key] = jvalue;
+        }
+
+        self.dirty = true;
+
+        Ok(())
+    }
+
+    ///Reads a value from the given key, resolving against any layers added through a `ConfigBuilder`, if the key does not exist returns `Err`
+    pub fn read_value<K: Into<String>, V: DeserializeOwned>(&self, key: K) -> Result<V, AnyError>{
+        let json = self.resolved().get(&key.into()).ok_or("Key not found")?.clone();
+        let value = serde_json::from_value::<V>(json)?;
+
+        Ok(value)
+    }
+
+    ///Writes a value at the given dotted path (e.g. `"sect0.val0"` or `"servers[0].host"`), creating intermediate `Section`s and arrays as needed
+    pub fn write_path<V: Serialize>(&mut self, path: &str, value: V) -> Result<(), AnyError>{
+        let segments = path::parse_path(path)?;
+        let jvalue = serde_json::to_value(value)?;
+
+        path::write_path(&mut self.data, &segments, jvalue)?;
+        self.dirty = true;
+
+        Ok(())
+    }
+
+    ///Reads a value at the given dotted path (e.g. `"sect0.val0"` or `"servers[0].host"`), resolving against any layers added through a `ConfigBuilder`, if any segment is missing returns `Err`
+    pub fn read_path<V: DeserializeOwned>(&self, path: &str) -> Result<V, AnyError>{
+        let segments = path::parse_path(path)?;
+        let json = path::read_path(&self.resolved(), &segments)?.clone();
+        let value = serde_json::from_value(json)?;
+
+        Ok(value)
+    }
+
+    ///Updates a value at the given dotted path using the provided function, resolving the pre-update value against
+    ///any layers added through a `ConfigBuilder` (same as `read_path`), returns the final value, if any segment is
+    ///missing returns `Err`
+    ///# Remarks
+    /// The updated value is only ever written into `data`; a key that only exists in a lower-precedence layer is
+    /// read as that layer's value but the update is still stored as an override in the writable layer
+    pub fn update_path<V, Out, F>(&mut self, path: &str, f_upd: F) -> Result<Out, AnyError>
+    where
+        V: DeserializeOwned,
+        Out: Serialize,
+        F: FnOnce(&V) -> Out,
+    {
+        let segments = path::parse_path(path)?;
+        let input = path::read_path(&self.resolved(), &segments)?.clone();
+        let value = serde_json::from_value(input)?;
+        let out = f_upd(&value);
+        let jvalue = serde_json::to_value(&out)?;
+        path::write_path(&mut self.data, &segments, jvalue)?;
+        self.dirty = true;
+
+        Ok(out)
+    }
+
+    ///Reads a value from the given key, if the key does not exists, inserts it with the given value
+    pub fn read_or_insert<K: Into<String>, V: DeserializeOwned + Serialize + Clone>(&mut self, key: K, value: V) -> Result<V, AnyError>{
+        let key = key.into();
+
+        let v = if let Entry::Vacant(e) = self.data.entry(&key) {
+            let jvalue = serde_json::to_value(value.clone())?;
+            e.insert(jvalue);
+            self.dirty = true;
+            value
+        }
+        else {
+            let jvalue = &self.data[&key];
+            serde_json::from_value(jvalue.clone())?
+        };
+
+        Ok(v)
+    }
+
+    ///Updates a value with the given key using the provided function, resolving the pre-update value against any
+    ///layers added through a `ConfigBuilder` (same as `read_value`), returns the final value of the key, if the key
+    ///does not exist returns Err
+    ///# Remarks
+    /// The updated value is only ever written into `data`; a key that only exists in a lower-precedence layer is
+    /// read as that layer's value but the update is still stored as an override in the writable layer
+    pub fn update_value<K, V, Out, F>(&mut self, key: &K, f_upd: F) -> Result<Out, AnyError>
+    where
+        K: ?Sized + Ord + Eq + Hash + ToOwned<Owned = String>,
+        String: Borrow<K>,
+        V: DeserializeOwned,
+        Out: Serialize,
+        F: FnOnce(&V) -> Out,
+    {
+        let input = self.resolved().get(key).ok_or("Key not found")?.clone();
+        let value = serde_json::from_value(input)?;
+        let out = f_upd(&value);
+        let jvalue = serde_json::to_value(&out)?;
+
+        //`key` may only exist in a lower-precedence layer, in which case `self.data` has no entry to index into yet
+        if let Entry::Vacant(e) = self.data.entry(key.to_owned()) {
+            e.insert(jvalue);
+        }
+        else {
+            self.data[key] = jvalue;
+        }
+        self.dirty = true;
+
+        Ok(out)
+    }
+
+    ///Restricts the config file and its containing directory to the owner only (mode `0600`/`0700` on Unix), and
+    ///keeps re-applying that after every `save()` since a rename-based write would otherwise reset the mode
+    ///# Remarks
+    /// No-op on non-Unix targets
+    pub fn set_secure(&mut self) -> Result<(), AnyError>{
+        self.secure = true;
+        self.enforce_secure_permissions()
+    }
+
+    ///Applies the `0600`/`0700` mode used by `set_secure`, if `self.secure` is set
+    #[cfg_attr(not(unix), allow(clippy::unnecessary_wraps))]
+    fn enforce_secure_permissions(&self) -> Result<(), AnyError>{
+        if !self.secure {
+            return Ok(());
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(0o600))?;
+
+            if let Some(dir) = self.path.parent() {
+                std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///Gets an immutable reference to `Section` at the given key
+    pub fn get_section<K>(&self, key: &K) -> Result<Section<&JObject>, AnyError>
+    where K: ?Sized + Ord + Eq + Hash, String: Borrow<K>{
+        let value = self.data.get(key).ok_or("Key not found")?
+        .as_object().ok_or("Key's Value is not a json object")?;
+
+        Ok(Section(value))
+    }
+
+    ///Gets a mutable reference to `Section` at the given key
+    ///# Remarks
+    /// Changing the `Section`'s value will also change the `Config` data
+    pub fn get_section_mut<K>(&mut self, key: &K) -> Result<Section<&mut JObject>, AnyError>
+    where K: ?Sized + Ord + Eq + Hash, String: Borrow<K>{
+        let value = self.data.get_mut(key).ok_or("Key not found")?
+        .as_object_mut().ok_or("Key's Value is not a json object")?;
+
+        Ok(Section(value))
+    }
+
+    ///Writes the `Config` object to the file, using whichever `Format` it was opened with
+    ///# Remarks
+    /// Writes to a sibling `.tmp` file, `fsync`s it, then renames it over the original so a crash mid-write or a
+    /// concurrent reader never observes a partial file. The advisory lock acquired on open is held the whole time,
+    /// so no other `Config` can be opened on the same path until this one is dropped.
+    pub fn save(&mut self) -> Result<String, AnyError>{
+        self.rotate_backups()?;
+
+        let str = self.format.to_string_pretty(&self.data)?;
+
+        let scratch_path = tmp_path(&self.path);
+        let mut scratch_file = OpenOptions::new().write(true).create(true).truncate(true).open(&scratch_path)?;
+        scratch_file.write_all(str.as_bytes())?;
+        scratch_file.sync_all()?;
+        drop(scratch_file);
+
+        std::fs::rename(&scratch_path, &self.path)?;
+        self.dirty = false;
+        self.enforce_secure_permissions()?;
+
+        Ok(str)
+    }
+
+    ///Shifts `path.{1..max_files-1}` up one slot and copies the current on-disk file to `path.1`, discarding whatever
+    ///was at `path.{max_files}`, ahead of `save()` overwriting `path`
+    ///# Remarks
+    /// No-op unless `with_backups` was set; if `with_backup_max_size` was also set, only rotates once the on-disk
+    /// file exceeds that size. Copies rather than renames `path` away so it keeps existing on disk right up until
+    /// `save()`'s final atomic rename of the new tmp file over it - otherwise a crash between rotation and that
+    /// rename would leave no file at `path` at all, defeating the crash-safety `save()` documents.
+    fn rotate_backups(&self) -> Result<(), AnyError>{
+        let Some(max_files) = self.backup_max_files.filter(|n| *n > 0) else { return Ok(()) };
+
+        if let Some(max_size) = self.backup_max_size {
+            let len = std::fs::metadata(&self.path)?.len();
+            if len <= max_size {
+                return Ok(());
+            }
+        }
+
+        for n in (1..max_files).rev() {
+            let from = backup_path(&self.path, n);
+            if from.exists() {
+                std::fs::rename(from, backup_path(&self.path, n + 1))?;
+            }
+        }
+
+        std::fs::copy(&self.path, backup_path(&self.path, 1))?;
+
+        Ok(())
+    }
+
+    ///Loads the `n`th rotated backup (as written by `save()` when `with_backups` is set) back into `data`, for
+    ///rolling back after a bad write
+    pub fn restore_backup(&mut self, n: u32) -> Result<(), AnyError>{
+        let str_content = std::fs::read_to_string(backup_path(&self.path, n))?;
+        self.data = if str_content.is_empty() { JObject::new() } else { self.format.parse(&str_content)? };
+        self.dirty = true;
+
+        Ok(())
+    }
+
+    ///Re-reads the on-disk file into `data`, in case another process atomically replaced it since this `Config` was opened or last saved
+    ///# Arguments
+    ///* `force` - If `false`, fails rather than discard unsaved edits made through `write_value`/`write_path`/`update_value`/`update_path`
+    ///# Remarks
+    /// Edits made through a borrowed `Section` aren't tracked, since a `Section` only holds a borrow into `data` and
+    /// never reports back to the `Config` it was obtained from; pass `force: true` if a `Section` may have been edited
+    pub fn reload(&mut self, force: bool) -> Result<(), AnyError>{
+        if self.dirty && !force {
+            return Err("Config has unsaved changes, pass force=true to discard them".into());
+        }
+
+        let str_content = std::fs::read_to_string(&self.path)?;
+        self.data = if str_content.is_empty() { JObject::new() } else { self.format.parse(&str_content)? };
+        self.dirty = false;
+
+        Ok(())
+    }
+
+    ///Clones the `Config` data, the result does not have any reference to the original `Config`
+    #[must_use]
+    pub fn clone_data(&self) -> JObject{
+        self.data.clone()
+    }
+
+    ///Replaces `Config` data with the provided data
+    pub fn copy_from(&mut self, data: JObject){
+        self.data = data;
+        self.dirty = true;
+    }
+
+}
+
+///Part of a `Config` object, may contain sub-sections
+#[derive(Debug)]
+pub struct Section<T: ?Sized + Borrow<JObject>>(T);
+
+impl<T: ?Sized + Borrow<JObject>> Section<T>{
+
+    ///Reads a value from the given key, if the key does not exist returns `Err`
+    pub fn read_value<K, V>(&self, key: &K) -> Result<V, AnyError>
+    where
+        K: ?Sized + Ord + Eq + Hash,
+        String: Borrow<K>,
+        V: DeserializeOwned
+    {
+        let json = self.0.borrow().get(key).ok_or("Key not found")?.clone();
+        let value = serde_json::from_value::<V>(json)?;
+
+        Ok(value)
+    }
+
+    ///Reads a value at the given dotted path (e.g. `"a.b.c"` or `"servers[0].host"`), if any segment is missing returns `Err`
+    pub fn read_path<V: DeserializeOwned>(&self, path: &str) -> Result<V, AnyError>{
+        let segments = path::parse_path(path)?;
+        let json = path::read_path(self.0.borrow(), &segments)?.clone();
+        let value = serde_json::from_value(json)?;
+
+        Ok(value)
+    }
+
+    ///Gets an immutable reference to `Section` at the given key
+    pub fn get_section<K>(&self, key: &K) -> Result<Section<&JObject>, AnyError>
+    where K: ?Sized + Ord + Eq + Hash, String: Borrow<K>{
+        let value = self.0.borrow().get(key).ok_or("Key not found")?
+        .as_object().ok_or("Key's Value is not a json object")?;
+
+        Ok(Section(value))
+    }
+
+    ///Clones the `Section` data, the result does not have any reference to the original `Config` nor `Section`
+    #[must_use]
+    pub fn clone_data(&self) -> JObject{
+        self.0.borrow().clone()
+    }
+    
+}
+
+impl<T: ?Sized + BorrowMut<JObject>> Section<T>{
+
+    ///Writes a valute to the given key, if it doesn't exist, inserts the key - value pair
+    pub fn write_value<K: Into<String>, V: Serialize>(&mut self, key: K, value: V) -> Result<(), AnyError>{
+        let key = key.into();
+        let jvalue = serde_json::to_value(value)?;
+
+        if let Entry::Vacant(e) = self.0.borrow_mut().entry(&key) {
+            e.insert(jvalue);
+        }
+        else {
+            self.0.borrow_mut()[&key] = jvalue;
+        }
+
+        Ok(())
+    }
+
+    ///Writes a value at the given dotted path (e.g. `"a.b.c"` or `"servers[0].host"`), creating intermediate `Section`s and arrays as needed
+    pub fn write_path<V: Serialize>(&mut self, path: &str, value: V) -> Result<(), AnyError>{
+        let segments = path::parse_path(path)?;
+        let jvalue = serde_json::to_value(value)?;
+
+        path::write_path(self.0.borrow_mut(), &segments, jvalue)
+    }
+
+    ///Updates a value at the given dotted path using the provided function, returns the final value, if any segment is missing returns `Err`
+    pub fn update_path<V, Out, F>(&mut self, path: &str, f_upd: F) -> Result<Out, AnyError>
+    where
+        V: DeserializeOwned,
+        Out: Serialize,
+        F: FnOnce(&V) -> Out,
+    {
+        let segments = path::parse_path(path)?;
+        let input = path::read_path(self.0.borrow(), &segments)?.clone();
+        let value = serde_json::from_value(input)?;
+        let out = f_upd(&value);
+        let jvalue = serde_json::to_value(&out)?;
+        path::write_path(self.0.borrow_mut(), &segments, jvalue)?;
+
+        Ok(out)
+    }
+
+    ///Reads a value from the given key, if the key does not exists, inserts it with the given value
+    pub fn read_or_insert<K: Into<String>, V: DeserializeOwned + Serialize + Clone>(&mut self, key: K, value: V) -> Result<V, AnyError>{
+        let key = key.into();
+
+        let v = if let Entry::Vacant(e) = self.0.borrow_mut().entry(&key) {
+            let jvalue = serde_json::to_value(value.clone())?;
+            e.insert(jvalue);
+            value
+        }
+        else {
+            let jvalue = &self.0.borrow()[&key];
+            serde_json::from_value(jvalue.clone())?
+        };
+
+        Ok(v)
+    }
+
+    ///Updates a value with the given key using the provided function, returns the final value of the key, if the key does not exist returns Err
+    pub fn update_value<K, V, Out, F>(&mut self, key: &K, f_upd: F) -> Result<Out, AnyError>
+    where
+        K: ?Sized + Ord + Eq + Hash,
+        String: Borrow<K>,
+        V: DeserializeOwned,
+        Out: Serialize,
+        F: FnOnce(&V) -> Out,
+    {
+        let input = self.0.borrow().get(key).ok_or("Key not found")?;
+        let value = serde_json::from_value(input.clone())?;
+        let out = f_upd(&value);
+        let jvalue = serde_json::to_value(&out)?;
+        self.0.borrow_mut()[key] = jvalue;
+
+        Ok(out)
+    }
+
+    ///Gets a mutable reference to `Section` at the given key
+    ///# Remarks
+    /// Changing the `Section`'s value will also change the `Config` data
+    pub fn get_section_mut<K>(&mut self, key: &K) -> Result<Section<&mut JObject>, AnyError>
+    where K: ?Sized + Ord + Eq + Hash, String: Borrow<K>{
+        let value = self.0.borrow_mut().get_mut(key).ok_or("Key not found")?
+        .as_object_mut().ok_or("Key's Value is not a json object")?;
+
+        Ok(Section(value))
+    }
+
+    ///Replaces `Section` data with the provided data
+    pub fn copy_from(&mut self, data: JObject){
+        self.0.borrow_mut().clear();
+
+        for (k, v) in data {
+            self.0.borrow_mut().insert(k, v);
+        }
+    }
+
+}
@@ -0,0 +1,56 @@
+use serde_json::Value;
+
+use crate::JObject;
+
+///Builds a config layer from process environment variables whose name starts with `prefix` followed by `_`
+///# Remarks
+/// `PREFIX_PORT=8080` maps to the top-level key `port`; `__` nests, so `PREFIX_DB__HOST=localhost` maps to `db.host`.
+/// The remainder after the prefix is lowercased. Values are parsed as JSON number/bool/null where possible, falling
+/// back to a JSON string otherwise. Iterates `vars_os()` rather than `vars()` and skips any var whose key or value
+/// isn't valid UTF-8, so a stray non-UTF8 var elsewhere in the process environment can't panic a caller of this
+/// opt-in feature.
+pub(crate) fn collect_layer(prefix: &str) -> JObject {
+    let marker = format!("{prefix}_");
+    let mut layer = JObject::new();
+
+    for (key, raw_value) in std::env::vars_os() {
+        let Some(key) = key.to_str() else { continue };
+        let Some(raw_value) = raw_value.to_str() else { continue };
+        let Some(rest) = key.strip_prefix(&marker) else { continue };
+
+        let segments: Vec<&str> = rest.split("__").collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            continue;
+        }
+
+        insert_nested(&mut layer, &segments, parse_value(&raw_value));
+    }
+
+    layer
+}
+
+///Parses an env var's raw string into a JSON number/bool/null, falling back to a plain JSON string for anything else
+fn parse_value(raw: &str) -> Value {
+    match serde_json::from_str(raw) {
+        Ok(value @ (Value::Number(_) | Value::Bool(_) | Value::Null)) => value,
+        _ => Value::String(raw.to_string()),
+    }
+}
+
+///Inserts `value` into `layer` at the dotted path spelled out by `segments`, lowercased, creating intermediate objects
+fn insert_nested(layer: &mut JObject, segments: &[&str], value: Value) {
+    let (head, rest) = segments.split_first().expect("segments is non-empty");
+    let key = head.to_lowercase();
+
+    if rest.is_empty() {
+        layer.insert(key, value);
+        return;
+    }
+
+    let entry = layer.entry(key).or_insert_with(|| Value::Object(JObject::new()));
+    if !entry.is_object() {
+        *entry = Value::Object(JObject::new());
+    }
+
+    insert_nested(entry.as_object_mut().expect("just ensured object"), rest, value);
+}
@@ -1,13 +1,25 @@
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    
+
     #[error("JSON: {0}")]
     Json(#[from] serde_json::Error),
 
     #[error("IO: {0}")]
     IO(#[from] std::io::Error),
 
+    #[cfg(feature = "toml")]
+    #[error("TOML: {0}")]
+    TomlParse(#[from] toml::de::Error),
+
+    #[cfg(feature = "toml")]
+    #[error("TOML: {0}")]
+    TomlSerialize(#[from] toml::ser::Error),
+
+    #[cfg(feature = "yaml")]
+    #[error("YAML: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
     #[error("Generic: {0}")]
     Config(&'static str)
 }
@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use crate::{AnyError, JObject};
+
+///Converts between a `Config`'s in-memory `JObject` model and a specific on-disk text serialization
+///# Remarks
+/// The in-memory model is always `JObject`, so implementations just round-trip through their own serde crate
+pub trait Format: std::fmt::Debug {
+    ///Parses file contents into the in-memory `JObject` model
+    fn parse(&self, input: &str) -> Result<JObject, AnyError>;
+
+    ///Serializes the in-memory `JObject` model back to file contents
+    fn to_string_pretty(&self, data: &JObject) -> Result<String, AnyError>;
+}
+
+///Reads and writes JSON, the crate's default wire format
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormat;
+
+impl Format for JsonFormat {
+    fn parse(&self, input: &str) -> Result<JObject, AnyError>{
+        Ok(serde_json::from_str(input)?)
+    }
+
+    fn to_string_pretty(&self, data: &JObject) -> Result<String, AnyError>{
+        Ok(serde_json::to_string_pretty(data)?)
+    }
+}
+
+///Reads and writes TOML
+#[cfg(feature = "toml")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TomlFormat;
+
+#[cfg(feature = "toml")]
+impl Format for TomlFormat {
+    fn parse(&self, input: &str) -> Result<JObject, AnyError>{
+        Ok(toml::from_str(input)?)
+    }
+
+    fn to_string_pretty(&self, data: &JObject) -> Result<String, AnyError>{
+        Ok(toml::to_string_pretty(data)?)
+    }
+}
+
+///Reads and writes YAML
+#[cfg(feature = "yaml")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YamlFormat;
+
+#[cfg(feature = "yaml")]
+impl Format for YamlFormat {
+    fn parse(&self, input: &str) -> Result<JObject, AnyError>{
+        Ok(serde_yaml::from_str(input)?)
+    }
+
+    fn to_string_pretty(&self, data: &JObject) -> Result<String, AnyError>{
+        Ok(serde_yaml::to_string(data)?)
+    }
+}
+
+///Picks a `Format` for the given file name by its extension, falling back to JSON when unknown or missing
+pub(crate) fn infer_format(file_name: &str) -> Box<dyn Format>{
+    match Path::new(file_name).extension().and_then(std::ffi::OsStr::to_str) {
+        #[cfg(feature = "toml")]
+        Some("toml") => Box::new(TomlFormat),
+
+        #[cfg(feature = "yaml")]
+        Some("yaml" | "yml") => Box::new(YamlFormat),
+
+        _ => Box::new(JsonFormat),
+    }
+}
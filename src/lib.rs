@@ -6,12 +6,27 @@
 
 #![warn(clippy::cargo)]
 
+mod builder;
 mod config;
+mod env;
 mod error;
+mod format;
+mod merge;
+mod path;
 
 pub type JObject = serde_json::Map<String, serde_json::Value>;
 
+///Error type returned by every fallible operation in this crate
+pub type AnyError = Error;
+
+pub use builder::ConfigBuilder;
 pub use config::*;
 pub use error::Error;
+pub use format::Format;
+#[cfg(feature = "toml")]
+pub use format::TomlFormat;
+#[cfg(feature = "yaml")]
+pub use format::YamlFormat;
+pub use format::JsonFormat;
 
 mod tests;
\ No newline at end of file
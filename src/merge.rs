@@ -0,0 +1,23 @@
+use serde_json::Value;
+
+use crate::JObject;
+
+///Recursively merges `overlay` into `base`, giving `overlay` precedence
+///# Remarks
+/// If both sides of a key are objects they are merged key-by-key, otherwise `overlay`'s value replaces `base`'s.
+/// Arrays replace unless `concat_arrays` is set, in which case `overlay`'s array is appended to `base`'s.
+pub(crate) fn merge_into(base: &mut JObject, overlay: &JObject, concat_arrays: bool) {
+    for (key, overlay_value) in overlay {
+        match base.get_mut(key) {
+            Some(Value::Object(base_obj)) if overlay_value.is_object() => {
+                merge_into(base_obj, overlay_value.as_object().expect("just checked is_object"), concat_arrays);
+            }
+            Some(Value::Array(base_arr)) if concat_arrays && overlay_value.is_array() => {
+                base_arr.extend(overlay_value.as_array().expect("just checked is_array").iter().cloned());
+            }
+            _ => {
+                base.insert(key.clone(), overlay_value.clone());
+            }
+        }
+    }
+}
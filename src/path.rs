@@ -0,0 +1,150 @@
+use serde_json::Value;
+
+use crate::{AnyError, JObject};
+
+///A single step of a dotted config path, either a map key or a bracketed array index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+///Splits a dotted path such as `"servers[0].host"` into its segments
+///# Remarks
+/// A literal dot inside a key can be escaped with `\.`
+pub(crate) fn parse_path(path: &str) -> Result<Vec<PathSegment>, AnyError> {
+    let mut segments = Vec::new();
+    let mut token = String::new();
+    let mut chars = path.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'.') => {
+                token.push('.');
+                chars.next();
+            }
+            '.' => {
+                push_token(&mut segments, &token)?;
+                token.clear();
+            }
+            _ => token.push(c),
+        }
+    }
+    push_token(&mut segments, &token)?;
+
+    if segments.is_empty() {
+        return Err("Path is empty".into());
+    }
+
+    if !matches!(segments[0], PathSegment::Key(_)) {
+        return Err("Path must start with a key".into());
+    }
+
+    Ok(segments)
+}
+
+///Parses a single `name[0][1]`-style token into a `Key` segment followed by zero or more `Index` segments
+fn push_token(segments: &mut Vec<PathSegment>, token: &str) -> Result<(), AnyError> {
+    if token.is_empty() {
+        return Err("Path contains an empty segment".into());
+    }
+
+    let name_end = token.find('[').unwrap_or(token.len());
+    let (name, mut rest) = token.split_at(name_end);
+
+    if !name.is_empty() {
+        segments.push(PathSegment::Key(name.to_string()));
+    }
+
+    while !rest.is_empty() {
+        if !rest.starts_with('[') {
+            return Err("Malformed array index in path".into());
+        }
+        let close = rest.find(']').ok_or("Malformed array index in path")?;
+        let index = rest[1..close].parse::<usize>().map_err(|_| "Malformed array index in path")?;
+        segments.push(PathSegment::Index(index));
+        rest = &rest[close + 1..];
+    }
+
+    Ok(())
+}
+
+///Traverses `root` following `segments`, returning `Err` as soon as a segment is missing
+pub(crate) fn read_path<'a>(root: &'a JObject, segments: &[PathSegment]) -> Result<&'a Value, AnyError> {
+    let (first, rest) = segments.split_first().ok_or("Path is empty")?;
+
+    let Some(PathSegment::Key(key)) = Some(first) else {
+        return Err("Path must start with a key".into());
+    };
+
+    let mut current = root.get(key).ok_or("Key not found")?;
+
+    for segment in rest {
+        current = match segment {
+            PathSegment::Key(key) => current.as_object().and_then(|o| o.get(key)),
+            PathSegment::Index(index) => current.as_array().and_then(|a| a.get(*index)),
+        }.ok_or("Key not found")?;
+    }
+
+    Ok(current)
+}
+
+///Writes `value` at `segments` into `root`, creating intermediate objects/arrays as needed
+///# Remarks
+/// Arrays are grown with `null` entries so the target index can be reached
+pub(crate) fn write_path(root: &mut JObject, segments: &[PathSegment], value: Value) -> Result<(), AnyError> {
+    let mut wrapper = Value::Object(std::mem::take(root));
+    write_path_value(&mut wrapper, segments, value)?;
+
+    *root = match wrapper {
+        Value::Object(obj) => obj,
+        _ => unreachable!("wrapper is always built as a json object"),
+    };
+
+    Ok(())
+}
+
+fn write_path_value(current: &mut Value, segments: &[PathSegment], value: Value) -> Result<(), AnyError> {
+    let (first, rest) = segments.split_first().ok_or("Path is empty")?;
+    let next_is_index = rest.first().is_some_and(|s| matches!(s, PathSegment::Index(_)));
+
+    match first {
+        PathSegment::Key(key) => {
+            if !current.is_object() {
+                *current = Value::Object(JObject::new());
+            }
+            let obj = current.as_object_mut().expect("just coerced into an object");
+
+            if rest.is_empty() {
+                obj.insert(key.clone(), value);
+                return Ok(());
+            }
+
+            let entry = obj.entry(key.clone())
+            .or_insert_with(|| if next_is_index { Value::Array(Vec::new()) } else { Value::Object(JObject::new()) });
+
+            write_path_value(entry, rest, value)
+        }
+        PathSegment::Index(index) => {
+            if !current.is_array() {
+                *current = Value::Array(Vec::new());
+            }
+            let arr = current.as_array_mut().expect("just coerced into an array");
+
+            while arr.len() <= *index {
+                arr.push(Value::Null);
+            }
+
+            if rest.is_empty() {
+                arr[*index] = value;
+                return Ok(());
+            }
+
+            if arr[*index].is_null() {
+                arr[*index] = if next_is_index { Value::Array(Vec::new()) } else { Value::Object(JObject::new()) };
+            }
+
+            write_path_value(&mut arr[*index], rest, value)
+        }
+    }
+}
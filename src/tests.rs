@@ -2,7 +2,7 @@
 
 use serde_json::json;
 
-use crate::config;
+use crate::{config, ConfigBuilder, JObject};
 
 #[test]
 fn test_main(){
@@ -22,7 +22,7 @@ fn test_main(){
 fn test_sections(){
 
     {
-        let mut conf = config::open_from_home(".lib_config", "conftest.json").unwrap();
+        let mut conf = config::open_from_home(".lib_config", "conftest_sections.json").unwrap();
 
         conf.write_value("sect0", json!({
             "val0" : 10,
@@ -33,7 +33,7 @@ fn test_sections(){
     }
 
     {
-        let conf = config::open_from_home(".lib_config", "conftest.json").unwrap();
+        let conf = config::open_from_home(".lib_config", "conftest_sections.json").unwrap();
 
         let sect0 = conf.get_section("sect0").unwrap();
         let val0 : i32 = sect0.read_value("val0").unwrap();
@@ -43,4 +43,233 @@ fn test_sections(){
         assert_eq!(val1, String::from("foo"));
     }
 
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_dotted_path(){
+
+    let mut conf = config::open_from_home(".lib_config", "conftest_path.json").unwrap();
+
+    conf.write_path("sect0.val0", 42).unwrap();
+    conf.write_path("sect0.arr[2]", "c").unwrap();
+
+    let val0: i32 = conf.read_path("sect0.val0").unwrap();
+    let arr0: serde_json::Value = conf.read_path("sect0.arr[0]").unwrap();
+    let arr2: String = conf.read_path("sect0.arr[2]").unwrap();
+
+    assert_eq!(val0, 42);
+    assert_eq!(arr0, serde_json::Value::Null);
+    assert_eq!(arr2, String::from("c"));
+
+    conf.save().unwrap();
+
+}
+
+#[test]
+fn test_dotted_path_escaped_dot(){
+
+    let mut conf = config::open_from_home(".lib_config", "conftest_path_escape.json").unwrap();
+
+    conf.write_path("a\\.b", "literal-dot-key").unwrap();
+    let val: String = conf.read_path("a\\.b").unwrap();
+
+    assert_eq!(val, String::from("literal-dot-key"));
+
+    conf.save().unwrap();
+
+}
+
+#[test]
+fn test_dotted_path_rejects_leading_index(){
+
+    let mut conf = config::open_from_home(".lib_config", "conftest_path_leading_index.json").unwrap();
+
+    assert!(conf.write_path("[0]", 5).is_err());
+    assert!(conf.write_path("[0].x", 5).is_err());
+
+}
+
+#[test]
+fn test_builder_layer_precedence(){
+
+    let mut defaults = JObject::new();
+    defaults.insert("val0".to_string(), json!(1));
+    defaults.insert("val1".to_string(), json!("default"));
+
+    let mut overrides = JObject::new();
+    overrides.insert("val0".to_string(), json!(2));
+
+    let conf = ConfigBuilder::new()
+    .with_layer(0, defaults)
+    .with_layer(10, overrides)
+    .build(5, ".lib_config", "conftest_builder.json").unwrap();
+
+    let val0: i32 = conf.read_value("val0").unwrap();
+    let val1: String = conf.read_value("val1").unwrap();
+
+    assert_eq!(val0, 2);
+    assert_eq!(val1, String::from("default"));
+
+}
+
+#[test]
+fn test_builder_update_value_resolves_layers(){
+
+    let mut defaults = JObject::new();
+    defaults.insert("val0".to_string(), json!(10));
+
+    let mut conf = ConfigBuilder::new()
+    .with_layer(0, defaults)
+    .build(5, ".lib_config", "conftest_builder_update.json").unwrap();
+
+    let out: i32 = conf.update_value("val0", |v: &i32| v + 1).unwrap();
+    let read_back: i32 = conf.read_value("val0").unwrap();
+
+    assert_eq!(out, 11);
+    assert_eq!(read_back, 11);
+
+}
+
+#[test]
+fn test_env_prefix_overlay(){
+
+    std::env::set_var("LIBCONFIGTEST_PORT", "9090");
+    std::env::set_var("LIBCONFIGTEST_DB__HOST", "localhost");
+
+    let conf = config::open_from_home(".lib_config", "conftest_env.json").unwrap()
+    .with_env_prefix("LIBCONFIGTEST");
+
+    let port: i32 = conf.read_value("port").unwrap();
+    let host: String = conf.read_path("db.host").unwrap();
+
+    assert_eq!(port, 9090);
+    assert_eq!(host, String::from("localhost"));
+
+    std::env::remove_var("LIBCONFIGTEST_PORT");
+    std::env::remove_var("LIBCONFIGTEST_DB__HOST");
+
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_round_trip(){
+
+    {
+        let mut conf = config::open_from_home(".lib_config", "conftest_format.toml").unwrap();
+
+        conf.write_value("val0", 10).unwrap();
+        conf.write_value("val1", "foo").unwrap();
+
+        conf.save().unwrap();
+    }
+
+    {
+        let conf = config::open_from_home(".lib_config", "conftest_format.toml").unwrap();
+
+        let val0: i32 = conf.read_value("val0").unwrap();
+        let val1: String = conf.read_value("val1").unwrap();
+
+        assert_eq!(val0, 10);
+        assert_eq!(val1, String::from("foo"));
+    }
+
+}
+
+#[test]
+fn test_reload(){
+
+    let mut conf = config::open_from_home(".lib_config", "conftest_reload.json").unwrap();
+
+    conf.write_value("val0", 1).unwrap();
+    conf.save().unwrap();
+
+    conf.write_value("val0", 2).unwrap();
+
+    assert!(conf.reload(false).is_err());
+
+    conf.reload(true).unwrap();
+    let val0: i32 = conf.read_value("val0").unwrap();
+
+    assert_eq!(val0, 1);
+
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_yaml_round_trip(){
+
+    {
+        let mut conf = config::open_from_home(".lib_config", "conftest_format.yaml").unwrap();
+
+        conf.write_value("val0", 10).unwrap();
+        conf.write_value("val1", "foo").unwrap();
+
+        conf.save().unwrap();
+    }
+
+    {
+        let conf = config::open_from_home(".lib_config", "conftest_format.yaml").unwrap();
+
+        let val0: i32 = conf.read_value("val0").unwrap();
+        let val1: String = conf.read_value("val1").unwrap();
+
+        assert_eq!(val0, 10);
+        assert_eq!(val1, String::from("foo"));
+    }
+
+}
+
+#[test]
+fn test_backup_rotation_and_restore(){
+
+    let mut conf = config::open_from_home(".lib_config", "conftest_backups.json").unwrap()
+    .with_backups(2);
+
+    conf.write_value("val0", 1).unwrap();
+    conf.save().unwrap();
+
+    conf.write_value("val0", 2).unwrap();
+    conf.save().unwrap();
+
+    conf.write_value("val0", 3).unwrap();
+    conf.save().unwrap();
+
+    let current: i32 = conf.read_value("val0").unwrap();
+    assert_eq!(current, 3);
+
+    conf.restore_backup(1).unwrap();
+    let backup1: i32 = conf.read_value("val0").unwrap();
+    assert_eq!(backup1, 2);
+
+    conf.restore_backup(2).unwrap();
+    let backup2: i32 = conf.read_value("val0").unwrap();
+    assert_eq!(backup2, 1);
+
+    //Only `max_files` backups are ever kept, older rotated-out generations are discarded
+    assert!(conf.restore_backup(3).is_err());
+
+}
+
+#[cfg(unix)]
+#[test]
+fn test_set_secure_permissions(){
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut conf = config::open_from_home(".lib_config", "conftest_secure.json").unwrap();
+
+    conf.set_secure().unwrap();
+    conf.write_value("val0", 1).unwrap();
+    conf.save().unwrap();
+
+    let dirs = directories::BaseDirs::new().unwrap();
+    let mut dir_path = dirs.home_dir().to_path_buf();
+    dir_path.push(".lib_config");
+
+    let dir_mode = std::fs::metadata(&dir_path).unwrap().permissions().mode() & 0o777;
+    assert_eq!(dir_mode, 0o700);
+
+    let file_path = dir_path.join("conftest_secure.json");
+    let file_mode = std::fs::metadata(&file_path).unwrap().permissions().mode() & 0o777;
+    assert_eq!(file_mode, 0o600);
+
+}